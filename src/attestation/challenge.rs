@@ -0,0 +1,153 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Challenge-response freshness binding for attestation.
+//!
+//! [`derive_entangled_id`](super::derive_entangled_id) and ring attestation
+//! both accept a caller-supplied nonce, which on its own allows a previously
+//! valid proof to be replayed verbatim. This module adopts the RCAR-style
+//! challenge handshake used by confidential-computing attestation services:
+//! the verifier issues a fresh [`Challenge`], the prover folds it into the
+//! id via [`bind_challenge`], and the verifier checks the binding and the
+//! challenge's freshness via [`verify_bound_attestation`].
+//!
+//! Because this crate is `no_std` and has no clock, `now` is an explicit
+//! input to every check here rather than read from the environment. That
+//! means the freshness policy itself can be committed as a public zkVM
+//! output alongside the attestation it gates.
+
+use crate::attestation::{EntangledId, EntangledIdComponents};
+use crate::error::{LogicError, LogicResult};
+
+/// A verifier-issued, single-use freshness challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    /// Random nonce chosen by the verifier for this challenge.
+    pub nonce: [u8; 32],
+    /// Epoch the challenge was issued in.
+    pub epoch: u64,
+    /// Epoch after which the challenge is no longer accepted.
+    pub expiry: u64,
+}
+
+/// Maximum number of epochs a challenge may lag behind `now` before it is
+/// treated as stale, independent of its explicit `expiry`.
+pub const MAX_EPOCH_SKEW: u64 = 2;
+
+/// A challenge-bound attestation, combining the original (unbound)
+/// `EntangledIdComponents` with the id produced by binding them to a
+/// challenge, so a verifier can recompute and check the binding.
+#[derive(Debug, Clone, Copy)]
+pub struct BoundAttestation {
+    /// The unbound components the prover originally derived.
+    pub components: EntangledIdComponents,
+    /// The id produced by [`bind_challenge`] over `components` and the
+    /// challenge the prover was issued.
+    pub bound_id: EntangledId,
+}
+
+/// Folds `challenge` into `components`, producing a fresh id that is only
+/// valid for this specific challenge.
+#[must_use]
+pub fn bind_challenge(components: &EntangledIdComponents, challenge: &Challenge) -> EntangledIdComponents {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"saorsa-logic/challenge-bound-id/v1");
+    hasher.update(&components.id);
+    hasher.update(&challenge.nonce);
+    hasher.update(&challenge.epoch.to_le_bytes());
+    EntangledIdComponents::from_id(*hasher.finalize().as_bytes())
+}
+
+/// Verifies that `proof.bound_id` was correctly derived by binding
+/// `proof.components` to `challenge`, and that `challenge` is still fresh at
+/// epoch `now`.
+///
+/// Returns the bound `EntangledIdComponents` on success.
+pub fn verify_bound_attestation(
+    proof: &BoundAttestation,
+    challenge: &Challenge,
+    now: u64,
+) -> LogicResult<EntangledIdComponents> {
+    if now > challenge.expiry || now.saturating_sub(challenge.epoch) > MAX_EPOCH_SKEW {
+        return Err(LogicError::Expired);
+    }
+    let expected = bind_challenge(&proof.components, challenge);
+    if expected.id == proof.bound_id {
+        Ok(expected)
+    } else {
+        Err(LogicError::InvalidProof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(epoch: u64, expiry: u64) -> Challenge {
+        Challenge {
+            nonce: [5u8; 32],
+            epoch,
+            expiry,
+        }
+    }
+
+    #[test]
+    fn verifies_honest_binding() {
+        let components = EntangledIdComponents::from_id([1u8; 32]);
+        let challenge = challenge(10, 20);
+        let bound = bind_challenge(&components, &challenge);
+        let proof = BoundAttestation {
+            components,
+            bound_id: bound.id,
+        };
+
+        let result = verify_bound_attestation(&proof, &challenge, 12).unwrap();
+        assert_eq!(result, bound);
+    }
+
+    #[test]
+    fn rejects_wrong_challenge() {
+        let components = EntangledIdComponents::from_id([1u8; 32]);
+        let issued = challenge(10, 20);
+        let bound = bind_challenge(&components, &issued);
+        let proof = BoundAttestation {
+            components,
+            bound_id: bound.id,
+        };
+
+        let other = Challenge { nonce: [9u8; 32], ..issued };
+        assert_eq!(
+            verify_bound_attestation(&proof, &other, 12),
+            Err(LogicError::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn rejects_expired_challenge() {
+        let components = EntangledIdComponents::from_id([1u8; 32]);
+        let issued = challenge(10, 20);
+        let bound = bind_challenge(&components, &issued);
+        let proof = BoundAttestation {
+            components,
+            bound_id: bound.id,
+        };
+
+        assert_eq!(verify_bound_attestation(&proof, &issued, 21), Err(LogicError::Expired));
+    }
+
+    #[test]
+    fn rejects_challenge_too_far_behind_now() {
+        let components = EntangledIdComponents::from_id([1u8; 32]);
+        let issued = challenge(10, 100);
+        let bound = bind_challenge(&components, &issued);
+        let proof = BoundAttestation {
+            components,
+            bound_id: bound.id,
+        };
+
+        let now = issued.epoch + MAX_EPOCH_SKEW + 1;
+        assert_eq!(verify_bound_attestation(&proof, &issued, now), Err(LogicError::Expired));
+    }
+}
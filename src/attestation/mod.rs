@@ -0,0 +1,113 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Entangled Attestation: proving a node runs authorized software.
+//!
+//! The core primitive is the `EntangledId`: a deterministic binding of a
+//! node's signing public key, the hash of the binary it is running, and a
+//! nonce. Two nodes running the same binary with the same key and nonce
+//! derive the same id, which is what lets the network detect double-signing
+//! without ever seeing the private key.
+//!
+//! This module covers direct attestation (the key and binary hash are both
+//! disclosed). Submodules build additional attestation modes on top of the
+//! same id:
+//!
+//! - [`ring`]: anonymous attestation that hides *which* authorized binary is
+//!   running.
+//! - [`challenge`]: binds an id to a verifier-issued challenge so it cannot
+//!   be replayed.
+//! - [`quorum`]: requires M-of-N trust anchors to co-attest the same id.
+//! - [`transparency`]: requires a binary hash to appear in a public,
+//!   append-only transparency log before it is trusted.
+//! - [`policy`]: evaluates disclosed attestation against a declarative
+//!   allowlist and trusted-root certificate chain.
+
+use crate::merkle;
+
+pub mod challenge;
+#[cfg(feature = "alloc")]
+pub mod policy;
+pub mod quorum;
+pub mod ring;
+#[cfg(feature = "alloc")]
+pub mod transparency;
+
+/// ML-DSA-65 public key bytes.
+pub type PublicKey = [u8; 1952];
+
+/// A deterministic binding of a node's identity, binary, and nonce.
+pub type EntangledId = merkle::Hash;
+
+/// Derives the `EntangledId` for a node running `binary_hash`, signed by
+/// `public_key`, with the given `nonce`.
+#[must_use]
+pub fn derive_entangled_id(public_key: &PublicKey, binary_hash: &[u8; 32], nonce: u64) -> EntangledId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"saorsa-logic/entangled-id/v1");
+    hasher.update(public_key);
+    hasher.update(binary_hash);
+    hasher.update(&nonce.to_le_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// Verifies that `entangled_id` was derived from `public_key`, `binary_hash`,
+/// and `nonce`.
+#[must_use]
+pub fn verify_entangled_id(
+    entangled_id: &EntangledId,
+    public_key: &PublicKey,
+    binary_hash: &[u8; 32],
+    nonce: u64,
+) -> bool {
+    &derive_entangled_id(public_key, binary_hash, nonce) == entangled_id
+}
+
+/// An `EntangledId` together with the context it was derived under.
+///
+/// Attestation modes that don't disclose the raw public key and binary hash
+/// (such as [`ring`]) still produce this type, so downstream consumers
+/// (quorum checks, policy evaluation) have one shape to work with regardless
+/// of which mode proved the id. `binary_hash`, `public_key`, and `epoch` are
+/// only populated for modes that disclose them; ring attestation leaves them
+/// `None`, so policy checks that need them only apply to direct attestation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntangledIdComponents {
+    /// The derived entangled identity.
+    pub id: EntangledId,
+    /// The disclosed binary hash, if this mode reveals it.
+    pub binary_hash: Option<[u8; 32]>,
+    /// The disclosed signing public key, if this mode reveals it.
+    pub public_key: Option<PublicKey>,
+    /// The disclosed nonce/epoch the id was derived under, if this mode
+    /// reveals it.
+    pub epoch: Option<u64>,
+}
+
+impl EntangledIdComponents {
+    /// Wraps an id with no disclosed context, e.g. the output of a ring-VRF
+    /// or challenge-bound derivation.
+    #[must_use]
+    pub fn from_id(id: EntangledId) -> Self {
+        Self {
+            id,
+            binary_hash: None,
+            public_key: None,
+            epoch: None,
+        }
+    }
+
+    /// Derives the id for, and wraps, a direct (non-anonymous) attestation
+    /// that discloses its public key, binary hash, and epoch/nonce.
+    #[must_use]
+    pub fn from_disclosed(public_key: &PublicKey, binary_hash: [u8; 32], epoch: u64) -> Self {
+        Self {
+            id: derive_entangled_id(public_key, &binary_hash, epoch),
+            binary_hash: Some(binary_hash),
+            public_key: Some(*public_key),
+            epoch: Some(epoch),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Attestation policy engine: binary allowlist and signer-chain validation.
+//!
+//! Inspired by Enarx Steward's validation configs, a [`Policy`] carries an
+//! allowlist of acceptable binary hashes, a set of trusted root signing keys,
+//! and an optional minimum epoch. [`evaluate`] checks a disclosed
+//! [`EntangledIdComponents`] against it: the binary hash must be allowed, and
+//! the node's signing key must chain up to a trusted root via a verified
+//! certificate path. This moves the accept/reject decision for "authorized
+//! software" out of ad-hoc caller code into a single provable, `no_std`
+//! evaluator that can itself run inside the zkVM.
+
+use crate::attestation::quorum::Signature;
+use crate::attestation::{EntangledIdComponents, PublicKey};
+use crate::error::{LogicError, LogicResult};
+use crate::merkle::{self, Hash, MerkleProof};
+
+/// The set of acceptable binary hashes a policy allows.
+#[derive(Debug, Clone, Copy)]
+pub enum BinaryAllowlist<'a> {
+    /// An explicit list of allowed binary hashes.
+    Explicit(&'a [Hash]),
+    /// A Merkle root committing to the allowed set; membership is checked
+    /// against `binary_inclusion_proof` passed to [`evaluate`].
+    Committed(Hash),
+}
+
+/// A declarative policy for accepting disclosed attestation.
+#[derive(Debug, Clone, Copy)]
+pub struct Policy<'a> {
+    /// Binary hashes this policy accepts.
+    pub allowlist: BinaryAllowlist<'a>,
+    /// Root signing keys a node's certificate chain must terminate at.
+    pub trusted_roots: &'a [PublicKey],
+    /// If set, the disclosed epoch/nonce must be at least this value.
+    pub min_epoch: Option<u64>,
+}
+
+/// A signed link in a certificate chain: `issuer_key` vouches for
+/// `subject_key` until `not_after`.
+#[derive(Debug, Clone, Copy)]
+pub struct CertificateLink {
+    /// The key that signed this link.
+    pub issuer_key: PublicKey,
+    /// The key being vouched for.
+    pub subject_key: PublicKey,
+    /// Epoch after which this link is no longer valid.
+    pub not_after: u64,
+    /// `issuer_key`'s signature over `(subject_key, not_after)`.
+    pub signature: Signature,
+}
+
+/// Why a [`Policy`] rejected an attestation, as a provable decision rather
+/// than a malformed-input error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenialReason {
+    /// No disclosed binary hash, or one absent from the allowlist.
+    BinaryNotAllowed,
+    /// The certificate chain does not terminate at a trusted root.
+    KeyNotTrusted,
+    /// The disclosed epoch is below `Policy::min_epoch`.
+    BelowMinimumEpoch,
+}
+
+/// The outcome of evaluating a [`Policy`] against disclosed components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyVerdict {
+    /// The components satisfy the policy.
+    Accepted,
+    /// The components do not satisfy the policy.
+    Denied(DenialReason),
+}
+
+/// Evaluates `components` against `policy`.
+///
+/// `cert_chain` must link `components`' disclosed public key up to one of
+/// `policy.trusted_roots`, in order (`cert_chain[0].subject_key` equal to the
+/// node's key, each subsequent link's `subject_key` equal to the previous
+/// link's `issuer_key`). `binary_inclusion_proof` is required, and checked,
+/// only when `policy.allowlist` is [`BinaryAllowlist::Committed`]. `now` is
+/// the caller-supplied current epoch, checked against each link's
+/// `not_after` since this crate is clock-free.
+///
+/// Malformed input (a chain that doesn't link, an unverifiable signature, an
+/// expired link) is a [`LogicError`]; a chain that verifies but simply
+/// doesn't satisfy the policy is a provable [`PolicyVerdict::Denied`].
+pub fn evaluate(
+    policy: &Policy<'_>,
+    components: &EntangledIdComponents,
+    cert_chain: &[CertificateLink],
+    binary_inclusion_proof: Option<&MerkleProof>,
+    now: u64,
+) -> LogicResult<PolicyVerdict> {
+    let Some(binary_hash) = components.binary_hash else {
+        return Ok(PolicyVerdict::Denied(DenialReason::BinaryNotAllowed));
+    };
+    let Some(public_key) = components.public_key else {
+        return Err(LogicError::InvalidInput);
+    };
+
+    if !binary_allowed(&policy.allowlist, &binary_hash, binary_inclusion_proof)? {
+        return Ok(PolicyVerdict::Denied(DenialReason::BinaryNotAllowed));
+    }
+
+    if let Some(min_epoch) = policy.min_epoch {
+        if components.epoch.is_none_or(|epoch| epoch < min_epoch) {
+            return Ok(PolicyVerdict::Denied(DenialReason::BelowMinimumEpoch));
+        }
+    }
+
+    if chains_to_trusted_root(policy, &public_key, cert_chain, now)? {
+        Ok(PolicyVerdict::Accepted)
+    } else {
+        Ok(PolicyVerdict::Denied(DenialReason::KeyNotTrusted))
+    }
+}
+
+fn binary_allowed(
+    allowlist: &BinaryAllowlist<'_>,
+    binary_hash: &Hash,
+    inclusion_proof: Option<&MerkleProof>,
+) -> LogicResult<bool> {
+    match allowlist {
+        BinaryAllowlist::Explicit(hashes) => Ok(hashes.contains(binary_hash)),
+        BinaryAllowlist::Committed(root) => {
+            let proof = inclusion_proof.ok_or(LogicError::InvalidInput)?;
+            let leaf = merkle::leaf_hash(binary_hash);
+            match merkle::verify_proof(&leaf, proof, root) {
+                Ok(()) => Ok(true),
+                Err(LogicError::InvalidProof) => Ok(false),
+                Err(e) => Err(e),
+            }
+        }
+    }
+}
+
+fn chains_to_trusted_root(
+    policy: &Policy<'_>,
+    node_key: &PublicKey,
+    cert_chain: &[CertificateLink],
+    now: u64,
+) -> LogicResult<bool> {
+    let mut expected_subject = *node_key;
+    for link in cert_chain {
+        if link.subject_key != expected_subject {
+            return Err(LogicError::InvalidInput);
+        }
+        if now > link.not_after {
+            return Err(LogicError::Expired);
+        }
+        let message = cert_link_message(link);
+        if !cert_link_signature_valid(&link.issuer_key, &message, &link.signature) {
+            return Err(LogicError::InvalidSignature);
+        }
+        expected_subject = link.issuer_key;
+    }
+    Ok(policy.trusted_roots.contains(&expected_subject))
+}
+
+fn cert_link_message(link: &CertificateLink) -> [u8; 1960] {
+    let mut message = [0u8; 1960];
+    message[..1952].copy_from_slice(&link.subject_key);
+    message[1952..].copy_from_slice(&link.not_after.to_le_bytes());
+    message
+}
+
+fn cert_link_signature_valid(issuer_key: &PublicKey, message: &[u8], signature: &Signature) -> bool {
+    crate::attestation::quorum::verify_ml_dsa(issuer_key, message, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::quorum::test_support::TestSigner;
+
+    fn key(tag: u8) -> PublicKey {
+        [tag; 1952]
+    }
+
+    fn link(issuer: &TestSigner, subject: PublicKey, not_after: u64) -> CertificateLink {
+        let mut link = CertificateLink {
+            issuer_key: issuer.public_key,
+            subject_key: subject,
+            not_after,
+            signature: [0u8; 3309],
+        };
+        link.signature = issuer.sign(&cert_link_message(&link));
+        link
+    }
+
+    fn components(binary_hash: [u8; 32], public_key: PublicKey, epoch: u64) -> EntangledIdComponents {
+        EntangledIdComponents::from_disclosed(&public_key, binary_hash, epoch)
+    }
+
+    #[test]
+    fn accepts_allowed_binary_and_trusted_chain() {
+        let node_key = key(1);
+        let root_key = TestSigner::seeded(9);
+        let policy = Policy {
+            allowlist: BinaryAllowlist::Explicit(&[[2u8; 32]]),
+            trusted_roots: &[root_key.public_key],
+            min_epoch: None,
+        };
+        let cert_chain = [link(&root_key, node_key, 100)];
+        let components = components([2u8; 32], node_key, 5);
+
+        let verdict = evaluate(&policy, &components, &cert_chain, None, 10).unwrap();
+        assert_eq!(verdict, PolicyVerdict::Accepted);
+    }
+
+    #[test]
+    fn denies_binary_not_on_allowlist() {
+        let node_key = key(1);
+        let root_key = TestSigner::seeded(9);
+        let policy = Policy {
+            allowlist: BinaryAllowlist::Explicit(&[[2u8; 32]]),
+            trusted_roots: &[root_key.public_key],
+            min_epoch: None,
+        };
+        let cert_chain = [link(&root_key, node_key, 100)];
+        let components = components([3u8; 32], node_key, 5);
+
+        let verdict = evaluate(&policy, &components, &cert_chain, None, 10).unwrap();
+        assert_eq!(verdict, PolicyVerdict::Denied(DenialReason::BinaryNotAllowed));
+    }
+
+    #[test]
+    fn denies_below_minimum_epoch() {
+        let node_key = key(1);
+        let root_key = TestSigner::seeded(9);
+        let policy = Policy {
+            allowlist: BinaryAllowlist::Explicit(&[[2u8; 32]]),
+            trusted_roots: &[root_key.public_key],
+            min_epoch: Some(10),
+        };
+        let cert_chain = [link(&root_key, node_key, 100)];
+        let components = components([2u8; 32], node_key, 5);
+
+        let verdict = evaluate(&policy, &components, &cert_chain, None, 10).unwrap();
+        assert_eq!(verdict, PolicyVerdict::Denied(DenialReason::BelowMinimumEpoch));
+    }
+
+    #[test]
+    fn denies_chain_not_terminating_at_trusted_root() {
+        let node_key = key(1);
+        let untrusted_root = TestSigner::seeded(8);
+        let trusted_root = TestSigner::seeded(9);
+        let policy = Policy {
+            allowlist: BinaryAllowlist::Explicit(&[[2u8; 32]]),
+            trusted_roots: &[trusted_root.public_key],
+            min_epoch: None,
+        };
+        let cert_chain = [link(&untrusted_root, node_key, 100)];
+        let components = components([2u8; 32], node_key, 5);
+
+        let verdict = evaluate(&policy, &components, &cert_chain, None, 10).unwrap();
+        assert_eq!(verdict, PolicyVerdict::Denied(DenialReason::KeyNotTrusted));
+    }
+
+    #[test]
+    fn rejects_expired_certificate_link() {
+        let node_key = key(1);
+        let root_key = TestSigner::seeded(9);
+        let policy = Policy {
+            allowlist: BinaryAllowlist::Explicit(&[[2u8; 32]]),
+            trusted_roots: &[root_key.public_key],
+            min_epoch: None,
+        };
+        let cert_chain = [link(&root_key, node_key, 5)];
+        let components = components([2u8; 32], node_key, 5);
+
+        assert_eq!(evaluate(&policy, &components, &cert_chain, None, 10), Err(LogicError::Expired));
+    }
+
+    #[test]
+    fn checks_binary_inclusion_against_committed_root() {
+        let node_key = key(1);
+        let root_key = TestSigner::seeded(9);
+        let binary_hash = [2u8; 32];
+        let leaf = merkle::leaf_hash(&binary_hash);
+        let mut frontier = merkle::Frontier::new();
+        frontier.append(leaf);
+        let root = frontier.root();
+        let proof = frontier.witness().unwrap();
+
+        let policy = Policy {
+            allowlist: BinaryAllowlist::Committed(root),
+            trusted_roots: &[root_key.public_key],
+            min_epoch: None,
+        };
+        let cert_chain = [link(&root_key, node_key, 100)];
+        let components = components(binary_hash, node_key, 5);
+
+        let verdict = evaluate(&policy, &components, &cert_chain, Some(&proof), 10).unwrap();
+        assert_eq!(verdict, PolicyVerdict::Accepted);
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! M-of-N threshold quorum attestation over `EntangledId`s.
+//!
+//! Modeled on Wormhole's guardian-set VAA scheme: given a configured
+//! validator set (an ordered list of ML-DSA-65 public keys) and a threshold
+//! `M`, [`verify_quorum_attestation`] checks that at least `M` distinct
+//! validators signed the same [`EntangledIdComponents`]. This lets
+//! `saorsa-core` accept an identity as authorized only once a quorum of the
+//! network's trust anchors has co-attested it, rather than trusting any
+//! single attestor.
+
+use signature::Verifier;
+
+use crate::attestation::{EntangledIdComponents, PublicKey};
+use crate::error::{LogicError, LogicResult};
+
+/// An ML-DSA-65 signature.
+pub type Signature = [u8; 3309];
+
+/// One validator's signature over an `EntangledIdComponents`, tagged with
+/// its position in the validator set.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexedSignature {
+    /// Index of the signing validator within [`ValidatorSet::members`].
+    pub validator_index: u32,
+    /// The ML-DSA-65 signature over the attested id.
+    pub signature: Signature,
+}
+
+/// The configured validator set, versioned so that a quorum attestation
+/// produced against a superseded set is rejected rather than silently
+/// accepted under looser membership.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidatorSet<'a> {
+    /// Monotonically increasing version, bumped whenever membership changes.
+    pub version: u64,
+    /// Ordered ML-DSA-65 public keys; a signature's `validator_index` is its
+    /// position in this slice.
+    pub members: &'a [PublicKey],
+}
+
+/// A compact quorum attestation: the validator-set version it was produced
+/// against, plus the per-validator signatures.
+#[derive(Debug, Clone, Copy)]
+pub struct QuorumAttestation<'a> {
+    /// The [`ValidatorSet::version`] these signatures were produced against.
+    pub set_version: u64,
+    /// Signatures, ordered by strictly increasing `validator_index`.
+    pub signatures: &'a [IndexedSignature],
+}
+
+/// Verifies that at least `threshold` distinct validators in `validator_set`
+/// signed `components`, per `attestation`.
+///
+/// Rejects if `attestation.set_version` does not match `validator_set`
+/// (a stale or superseded set), if `attestation.signatures` are not in
+/// strictly increasing `validator_index` order (which also rejects
+/// duplicates), or if fewer than `threshold` signatures verify. Verification
+/// short-circuits as soon as `threshold` valid signatures have been counted.
+pub fn verify_quorum_attestation(
+    components: &EntangledIdComponents,
+    attestation: &QuorumAttestation<'_>,
+    validator_set: &ValidatorSet<'_>,
+    threshold: usize,
+) -> LogicResult<()> {
+    if attestation.set_version != validator_set.version {
+        return Err(LogicError::InvalidInput);
+    }
+
+    let mut valid = 0usize;
+    let mut last_index: Option<u32> = None;
+    for sig in attestation.signatures {
+        if last_index.is_some_and(|last| sig.validator_index <= last) {
+            return Err(LogicError::InvalidInput);
+        }
+        last_index = Some(sig.validator_index);
+
+        let Some(member) = validator_set.members.get(sig.validator_index as usize) else {
+            return Err(LogicError::InvalidInput);
+        };
+        if verify_ml_dsa(member, &components.id, &sig.signature) {
+            valid += 1;
+            if valid >= threshold {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(LogicError::InvalidSignature)
+}
+
+/// Verifies an ML-DSA-65 signature over `message` by `public_key`.
+///
+/// Shared by every attestation mode that checks a signature against a
+/// disclosed public key ([`crate::attestation::transparency`],
+/// [`crate::attestation::policy`]), so there is exactly one decode-and-verify
+/// path to get right, rather than one per caller.
+pub(crate) fn verify_ml_dsa(public_key: &PublicKey, message: &[u8], signature: &Signature) -> bool {
+    let Ok(verifying_key) = ml_dsa::VerifyingKey::<ml_dsa::MlDsa65>::decode(public_key) else {
+        return false;
+    };
+    let Ok(signature) = ml_dsa::Signature::<ml_dsa::MlDsa65>::decode(signature) else {
+        return false;
+    };
+    verifying_key.verify(message, &signature).is_ok()
+}
+
+/// Test-only ML-DSA-65 keypair generation and signing, shared across the
+/// attestation modules that verify real signatures
+/// ([`crate::attestation::transparency`], [`crate::attestation::policy`]) so
+/// each of them exercises the same, non-test-gated [`verify_ml_dsa`] against
+/// a genuinely valid signature rather than a hand-rolled stand-in.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use ml_dsa::{KeyGen, MlDsa65, SigningKey};
+    use rand_core::{CryptoRng, RngCore};
+    use signature::Signer;
+
+    use super::{PublicKey, Signature};
+
+    /// A deterministic, non-cryptographic byte stream (splitmix64) used only
+    /// to make test keypairs reproducible without pulling in a `rand`
+    /// dependency. Never use outside tests.
+    pub(crate) struct DeterministicRng(u64);
+
+    impl DeterministicRng {
+        pub(crate) fn seeded(seed: u64) -> Self {
+            Self(seed)
+        }
+
+        fn next_word(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+    }
+
+    impl RngCore for DeterministicRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_word() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.next_word()
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            for chunk in dst.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next_word().to_le_bytes()[..chunk.len()]);
+            }
+        }
+    }
+
+    impl CryptoRng for DeterministicRng {}
+
+    /// A real ML-DSA-65 keypair, generated deterministically from `seed`, for
+    /// signing test messages and exercising [`super::verify_ml_dsa`] against
+    /// genuine (and genuinely invalid) signatures.
+    pub(crate) struct TestSigner {
+        signing_key: SigningKey<MlDsa65>,
+        pub(crate) public_key: PublicKey,
+    }
+
+    impl TestSigner {
+        pub(crate) fn seeded(seed: u64) -> Self {
+            let mut rng = DeterministicRng::seeded(seed);
+            let keypair = MlDsa65::key_gen(&mut rng);
+            let public_key = keypair
+                .verifying_key()
+                .encode()
+                .as_ref()
+                .try_into()
+                .expect("ML-DSA-65 verifying key encodes to PublicKey's length");
+            Self {
+                signing_key: keypair.signing_key().clone(),
+                public_key,
+            }
+        }
+
+        pub(crate) fn sign(&self, message: &[u8]) -> Signature {
+            self.signing_key
+                .sign(message)
+                .encode()
+                .as_ref()
+                .try_into()
+                .expect("ML-DSA-65 signature encodes to Signature's length")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::TestSigner;
+    use super::*;
+    use crate::attestation::EntangledIdComponents;
+
+    fn components() -> EntangledIdComponents {
+        EntangledIdComponents::from_id([42u8; 32])
+    }
+
+    fn sign(index: u32, signer: &TestSigner, message: &[u8]) -> IndexedSignature {
+        IndexedSignature {
+            validator_index: index,
+            signature: signer.sign(message),
+        }
+    }
+
+    #[test]
+    fn accepts_quorum_of_distinct_signers() {
+        let signers = [TestSigner::seeded(1), TestSigner::seeded(2), TestSigner::seeded(3)];
+        let members = [signers[0].public_key, signers[1].public_key, signers[2].public_key];
+        let set = ValidatorSet { version: 1, members: &members };
+        let comps = components();
+        let signatures = [sign(0, &signers[0], &comps.id), sign(2, &signers[2], &comps.id)];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert!(verify_quorum_attestation(&comps, &attestation, &set, 2).is_ok());
+    }
+
+    #[test]
+    fn rejects_signature_from_wrong_validator() {
+        let signers = [TestSigner::seeded(1), TestSigner::seeded(2)];
+        let members = [signers[0].public_key, signers[1].public_key];
+        let set = ValidatorSet { version: 1, members: &members };
+        let comps = components();
+        // Index 1's signature slot is filled with validator 0's signature.
+        let signatures = [sign(1, &signers[0], &comps.id)];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert_eq!(
+            verify_quorum_attestation(&comps, &attestation, &set, 1),
+            Err(LogicError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn short_circuits_once_threshold_is_met() {
+        let signers = [TestSigner::seeded(1), TestSigner::seeded(2), TestSigner::seeded(3)];
+        let members = [signers[0].public_key, signers[1].public_key, signers[2].public_key];
+        let set = ValidatorSet { version: 1, members: &members };
+        let comps = components();
+        // The third entry names a validator index outside the set, which
+        // fails with `InvalidInput` if inspected; the test only passes if
+        // verification stops after the threshold of 2 valid signatures.
+        let bogus = IndexedSignature {
+            validator_index: 3,
+            signature: [0xFFu8; 3309],
+        };
+        let signatures = [sign(0, &signers[0], &comps.id), sign(1, &signers[1], &comps.id), bogus];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert!(verify_quorum_attestation(&comps, &attestation, &set, 2).is_ok());
+    }
+
+    #[test]
+    fn rejects_stale_validator_set_version() {
+        let signer = TestSigner::seeded(1);
+        let members = [signer.public_key];
+        let set = ValidatorSet { version: 2, members: &members };
+        let comps = components();
+        let signatures = [sign(0, &signer, &comps.id)];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert_eq!(
+            verify_quorum_attestation(&comps, &attestation, &set, 1),
+            Err(LogicError::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_validator_index() {
+        let signers = [TestSigner::seeded(1), TestSigner::seeded(2)];
+        let members = [signers[0].public_key, signers[1].public_key];
+        let set = ValidatorSet { version: 1, members: &members };
+        let comps = components();
+        let signatures = [sign(0, &signers[0], &comps.id), sign(0, &signers[0], &comps.id)];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert_eq!(
+            verify_quorum_attestation(&comps, &attestation, &set, 2),
+            Err(LogicError::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_order_validator_index() {
+        let signers = [TestSigner::seeded(1), TestSigner::seeded(2)];
+        let members = [signers[0].public_key, signers[1].public_key];
+        let set = ValidatorSet { version: 1, members: &members };
+        let comps = components();
+        let signatures = [sign(1, &signers[1], &comps.id), sign(0, &signers[0], &comps.id)];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert_eq!(
+            verify_quorum_attestation(&comps, &attestation, &set, 2),
+            Err(LogicError::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn rejects_below_threshold_valid_signatures() {
+        let signers = [TestSigner::seeded(1), TestSigner::seeded(2)];
+        let members = [signers[0].public_key, signers[1].public_key];
+        let set = ValidatorSet { version: 1, members: &members };
+        let comps = components();
+        let signatures = [sign(0, &signers[0], &comps.id)];
+        let attestation = QuorumAttestation { set_version: 1, signatures: &signatures };
+
+        assert_eq!(
+            verify_quorum_attestation(&comps, &attestation, &set, 2),
+            Err(LogicError::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Ring-VRF anonymous attestation.
+//!
+//! A node proves "I am running one of the authorized binaries" without
+//! revealing *which* one, modeled on the ring-VRF tickets used by Sassafras.
+//! The verifier publishes a ring: the ordered set of authorized
+//! `(binary_hash, public_key)` pairs, committed to as a Merkle root (the
+//! [`RingCommitment`]) via [`crate::merkle`]. The prover computes a VRF over
+//! the challenge, keyed by its secret, and produces a proof that the output
+//! was generated honestly by *some* member of the ring, without disclosing
+//! the index.
+//!
+//! The ring-membership and VRF math themselves are elliptic-curve operations
+//! (e.g. Bandersnatch ring-VRF) that this crate does not implement directly:
+//! like other expensive curve arithmetic, they are best run as a zkVM
+//! precompile rather than interpreted in-guest. This module instead defines
+//! the [`RingVrfBackend`] trait the host provides that primitive through, and
+//! composes it with the deterministic bookkeeping (binding the output into
+//! an [`EntangledIdComponents`]) that must run in-guest to be proven.
+
+use crate::attestation::{EntangledId, EntangledIdComponents};
+use crate::error::{LogicError, LogicResult};
+use crate::merkle::Hash;
+
+/// Merkle root committing to the ring's `(binary_hash, public_key)` members.
+pub type RingCommitment = Hash;
+
+/// A ring-VRF proof: the VRF output plus the opaque curve-level proof that it
+/// was computed honestly by a ring member, without revealing which one.
+#[derive(Debug, Clone, Copy)]
+pub struct RingVrfProof<'a> {
+    /// The VRF output. Becomes the basis of the resulting `EntangledId`, so
+    /// that repeated attestation from the same node is detectable.
+    pub vrf_output: Hash,
+    /// Opaque ring signature proof, verified by a [`RingVrfBackend`].
+    pub proof_bytes: &'a [u8],
+}
+
+/// Verifies the elliptic-curve half of a ring-VRF proof.
+///
+/// Implementations perform the actual Bandersnatch ring-VRF verification;
+/// this crate only defines the interface so the deterministic composition in
+/// [`verify_ring_attestation`] stays independent of the curve library used.
+pub trait RingVrfBackend {
+    /// Returns `true` if `proof` shows `vrf_output` was honestly computed by
+    /// some member of the ring committed to by `ring_commitment`, over
+    /// `challenge`.
+    fn verify(&self, ring_commitment: &RingCommitment, challenge: &[u8], proof: &RingVrfProof<'_>) -> bool;
+}
+
+/// Derives the `EntangledId` for a ring-VRF attestation.
+///
+/// The id is bound to the VRF output and the ring context (`ring_commitment`,
+/// `nonce`); `nonce` is expected to be the ring's current epoch or version
+/// rather than a fresh value per call, so that two proofs produced by the
+/// same node's VRF secret within that epoch collide on the same id — this is
+/// what lets the network detect double-signing without learning which binary
+/// hash was attested to.
+#[must_use]
+pub fn derive_ring_entangled_id(vrf_output: &Hash, ring_commitment: &RingCommitment, nonce: u64) -> EntangledIdComponents {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"saorsa-logic/ring-entangled-id/v1");
+    hasher.update(vrf_output);
+    hasher.update(ring_commitment);
+    hasher.update(&nonce.to_le_bytes());
+    let id: EntangledId = *hasher.finalize().as_bytes();
+    EntangledIdComponents::from_id(id)
+}
+
+/// Verifies a ring-VRF attestation and returns the resulting
+/// [`EntangledIdComponents`] on success.
+///
+/// `challenge` is the verifier-chosen input the VRF was evaluated over (see
+/// [`crate::attestation::challenge`] for freshness-bound challenges). The
+/// `nonce` bound into the returned id is the ring commitment's epoch, so
+/// callers that need per-challenge context should derive `challenge` to
+/// include it.
+pub fn verify_ring_attestation(
+    proof: &RingVrfProof<'_>,
+    ring_commitment: &RingCommitment,
+    challenge: &[u8],
+    backend: &impl RingVrfBackend,
+    epoch: u64,
+) -> LogicResult<EntangledIdComponents> {
+    if backend.verify(ring_commitment, challenge, proof) {
+        Ok(derive_ring_entangled_id(&proof.vrf_output, ring_commitment, epoch))
+    } else {
+        Err(LogicError::InvalidProof)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    /// A [`RingVrfBackend`] standing in for real Bandersnatch ring-VRF
+    /// verification, so the deterministic composition in this module is
+    /// exercised without pulling in curve arithmetic. Accepts a proof iff its
+    /// `proof_bytes` is exactly `challenge` appended to `ring_commitment`,
+    /// which is enough to distinguish a genuine proof from a forged one in
+    /// tests without claiming to model real ring membership.
+    struct MockRingVrfBackend;
+
+    impl RingVrfBackend for MockRingVrfBackend {
+        fn verify(&self, ring_commitment: &RingCommitment, challenge: &[u8], proof: &RingVrfProof<'_>) -> bool {
+            let mut expected = ring_commitment.to_vec();
+            expected.extend_from_slice(challenge);
+            proof.proof_bytes == expected.as_slice()
+        }
+    }
+
+    fn honest_proof(proof_bytes: &[u8]) -> RingVrfProof<'_> {
+        RingVrfProof {
+            vrf_output: [7u8; 32],
+            proof_bytes,
+        }
+    }
+
+    #[test]
+    fn verifies_honest_proof_and_discloses_no_fields() {
+        let ring_commitment: RingCommitment = [1u8; 32];
+        let challenge = b"round-7";
+        let mut proof_bytes = ring_commitment.to_vec();
+        proof_bytes.extend_from_slice(challenge);
+        let proof = honest_proof(&proof_bytes);
+
+        let components = verify_ring_attestation(&proof, &ring_commitment, challenge, &MockRingVrfBackend, 3).unwrap();
+        assert_eq!(components, derive_ring_entangled_id(&proof.vrf_output, &ring_commitment, 3));
+        assert!(components.binary_hash.is_none());
+        assert!(components.public_key.is_none());
+        assert!(components.epoch.is_none());
+    }
+
+    #[test]
+    fn rejects_forged_proof() {
+        let ring_commitment: RingCommitment = [1u8; 32];
+        let challenge = b"round-7";
+        let proof = honest_proof(b"not-the-expected-bytes");
+
+        let result = verify_ring_attestation(&proof, &ring_commitment, challenge, &MockRingVrfBackend, 3);
+        assert_eq!(result, Err(LogicError::InvalidProof));
+    }
+
+    #[test]
+    fn same_vrf_output_and_epoch_collide_regardless_of_challenge() {
+        let ring_commitment: RingCommitment = [1u8; 32];
+        let attest = |challenge: &[u8]| {
+            let mut proof_bytes = ring_commitment.to_vec();
+            proof_bytes.extend_from_slice(challenge);
+            let proof = RingVrfProof {
+                vrf_output: [9u8; 32],
+                proof_bytes: &proof_bytes,
+            };
+            verify_ring_attestation(&proof, &ring_commitment, challenge, &MockRingVrfBackend, 3).unwrap()
+        };
+        let id_a = attest(b"round-7");
+        let id_b = attest(b"round-8");
+        assert_eq!(id_a, id_b, "same node re-attesting within an epoch must be detectable");
+    }
+}
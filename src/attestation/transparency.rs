@@ -0,0 +1,274 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Signed transparency-log inclusion gate for binary hashes.
+//!
+//! Following the Rekor/sigstore model, a `binary_hash` is only accepted if it
+//! appears in a signed, append-only transparency log: [`verify_binary_in_log`]
+//! checks a Merkle inclusion proof of the hash against a
+//! [`SignedCheckpoint`]'s tree root (reusing [`crate::merkle`]) and then the
+//! checkpoint's own signature, so a log operator cannot attest to a binary
+//! that was never published. [`verify_consistency`] additionally lets a node
+//! confirm the log only ever appended between two checkpoints it observed,
+//! preventing a rewritten history from hiding a prior entry.
+
+use alloc::vec::Vec;
+
+use crate::attestation::quorum::Signature;
+use crate::attestation::PublicKey;
+use crate::error::{LogicError, LogicResult};
+use crate::merkle::{self, Hash, MerkleProof};
+
+/// The transparency log operator's ML-DSA-65 public key.
+pub type LogPublicKey = PublicKey;
+
+/// A signed, append-only-log checkpoint: the tree size and root hash at some
+/// point in the log's history, signed by the log operator.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedCheckpoint {
+    /// Number of entries in the log at this checkpoint.
+    pub tree_size: u64,
+    /// Merkle root over all `tree_size` entries.
+    pub root_hash: Hash,
+    /// The log operator's signature over `tree_size` and `root_hash`.
+    pub signature: Signature,
+}
+
+impl SignedCheckpoint {
+    fn signed_message(&self) -> [u8; 40] {
+        let mut message = [0u8; 40];
+        message[..8].copy_from_slice(&self.tree_size.to_le_bytes());
+        message[8..].copy_from_slice(&self.root_hash);
+        message
+    }
+}
+
+/// A consistency proof between two checkpoints, per RFC 6962 section 2.1.2.
+#[derive(Debug, Clone)]
+pub struct ConsistencyProof {
+    /// Proof hashes, in the order defined by the RFC 6962 algorithm.
+    pub nodes: Vec<Hash>,
+}
+
+/// Verifies that `binary_hash` is included in the log at `signed_checkpoint`,
+/// and that the checkpoint itself is validly signed by `log_pubkey`.
+pub fn verify_binary_in_log(
+    binary_hash: &Hash,
+    inclusion_proof: &MerkleProof,
+    signed_checkpoint: &SignedCheckpoint,
+    log_pubkey: &LogPublicKey,
+) -> LogicResult<()> {
+    let leaf = merkle::leaf_hash(binary_hash);
+    merkle::verify_proof(&leaf, inclusion_proof, &signed_checkpoint.root_hash)?;
+    verify_checkpoint_signature(signed_checkpoint, log_pubkey)
+}
+
+fn verify_checkpoint_signature(checkpoint: &SignedCheckpoint, log_pubkey: &LogPublicKey) -> LogicResult<()> {
+    let message = checkpoint.signed_message();
+    if crate::attestation::quorum::verify_ml_dsa(log_pubkey, &message, &checkpoint.signature) {
+        Ok(())
+    } else {
+        Err(LogicError::InvalidSignature)
+    }
+}
+
+/// Verifies that `new_checkpoint` is an append-only extension of
+/// `old_checkpoint`, i.e. every entry committed to by `old_checkpoint` is
+/// still present, in the same order, under `new_checkpoint`'s root.
+///
+/// Implements the RFC 6962 consistency-proof verification algorithm.
+pub fn verify_consistency(
+    old_checkpoint: &SignedCheckpoint,
+    new_checkpoint: &SignedCheckpoint,
+    proof: &ConsistencyProof,
+) -> LogicResult<()> {
+    let old_size = old_checkpoint.tree_size;
+    let new_size = new_checkpoint.tree_size;
+    if old_size > new_size {
+        return Err(LogicError::InvalidInput);
+    }
+    if old_size == new_size {
+        return if proof.nodes.is_empty() && old_checkpoint.root_hash == new_checkpoint.root_hash {
+            Ok(())
+        } else {
+            Err(LogicError::InvalidProof)
+        };
+    }
+    if old_size == 0 {
+        // An empty log is trivially a prefix of any log.
+        return Ok(());
+    }
+
+    let mut nodes = proof.nodes.iter();
+    let mut fn_idx = old_size - 1;
+    let mut sn_idx = new_size - 1;
+    while fn_idx & 1 == 1 {
+        fn_idx >>= 1;
+        sn_idx >>= 1;
+    }
+
+    let mut first_hash;
+    let mut second_hash;
+    if fn_idx > 0 {
+        first_hash = *nodes.next().ok_or(LogicError::InvalidProof)?;
+    } else {
+        first_hash = old_checkpoint.root_hash;
+    }
+    second_hash = first_hash;
+
+    for node in nodes {
+        if sn_idx == 0 {
+            return Err(LogicError::InvalidProof);
+        }
+        if fn_idx & 1 == 1 || fn_idx == sn_idx {
+            first_hash = merkle::combine(node, &first_hash);
+            second_hash = merkle::combine(node, &second_hash);
+            while fn_idx & 1 == 0 && fn_idx != 0 {
+                fn_idx >>= 1;
+                sn_idx >>= 1;
+            }
+        } else {
+            second_hash = merkle::combine(&second_hash, node);
+        }
+        fn_idx >>= 1;
+        sn_idx >>= 1;
+    }
+
+    if sn_idx != 0 {
+        return Err(LogicError::InvalidProof);
+    }
+    if first_hash == old_checkpoint.root_hash && second_hash == new_checkpoint.root_hash {
+        Ok(())
+    } else {
+        Err(LogicError::InvalidProof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::quorum::test_support::TestSigner;
+
+    /// RFC 6962 `MTH`: the Merkle tree hash of `leaves[0..n]`.
+    fn mth(leaves: &[Hash]) -> Hash {
+        match leaves.len() {
+            0 => merkle::leaf_hash(&[]),
+            1 => leaves[0],
+            n => {
+                let k = largest_power_of_two_less_than(n);
+                merkle::combine(&mth(&leaves[..k]), &mth(&leaves[k..]))
+            }
+        }
+    }
+
+    /// RFC 6962 `PROOF(m, D)`: the consistency proof nodes between the first
+    /// `m` leaves of `leaves` and all of `leaves`.
+    fn consistency_proof_nodes(m: usize, leaves: &[Hash]) -> Vec<Hash> {
+        subproof(m, leaves, true)
+    }
+
+    fn subproof(m: usize, leaves: &[Hash], top: bool) -> Vec<Hash> {
+        let n = leaves.len();
+        if m == n {
+            let mut single = Vec::new();
+            if !top {
+                single.push(mth(leaves));
+            }
+            return single;
+        }
+        let k = largest_power_of_two_less_than(n);
+        if m <= k {
+            let mut proof = subproof(m, &leaves[..k], top);
+            proof.push(mth(&leaves[k..]));
+            proof
+        } else {
+            let mut proof = subproof(m - k, &leaves[k..], false);
+            proof.push(mth(&leaves[..k]));
+            proof
+        }
+    }
+
+    fn largest_power_of_two_less_than(n: usize) -> usize {
+        let mut k = 1;
+        while k * 2 < n {
+            k *= 2;
+        }
+        k
+    }
+
+    fn leaves(n: usize) -> Vec<Hash> {
+        (0..n).map(|i| merkle::leaf_hash(&(i as u64).to_le_bytes())).collect()
+    }
+
+    fn checkpoint(signer: &TestSigner, tree_size: u64, root_hash: Hash) -> SignedCheckpoint {
+        let mut checkpoint = SignedCheckpoint {
+            tree_size,
+            root_hash,
+            signature: [0u8; 3309],
+        };
+        checkpoint.signature = signer.sign(&checkpoint.signed_message());
+        checkpoint
+    }
+
+    #[test]
+    fn verifies_binary_inclusion() {
+        // `verify_binary_in_log` folds the proof with `merkle::verify_proof`,
+        // which reads each sibling's side from the bits of `leaf_index`; a
+        // `merkle::Frontier` build produces a root and proof of that shape.
+        let log_key = TestSigner::seeded(4);
+        let binary_hash = [6u8; 32];
+
+        let mut frontier = merkle::Frontier::new();
+        frontier.append(merkle::leaf_hash(&[1u8; 32]));
+        frontier.append(merkle::leaf_hash(&binary_hash));
+        let proof = frontier.witness().unwrap();
+        let signed = checkpoint(&log_key, frontier.len(), frontier.root());
+
+        assert!(verify_binary_in_log(&binary_hash, &proof, &signed, &log_key.public_key).is_ok());
+    }
+
+    #[test]
+    fn consistency_proof_round_trip() {
+        let log_key = TestSigner::seeded(4);
+        let all = leaves(7);
+        let old_root = mth(&all[..4]);
+        let new_root = mth(&all);
+        let old_checkpoint = checkpoint(&log_key, 4, old_root);
+        let new_checkpoint = checkpoint(&log_key, 7, new_root);
+
+        let proof = ConsistencyProof {
+            nodes: consistency_proof_nodes(4, &all),
+        };
+        assert!(verify_consistency(&old_checkpoint, &new_checkpoint, &proof).is_ok());
+    }
+
+    #[test]
+    fn consistency_rejects_tampered_proof() {
+        let log_key = TestSigner::seeded(4);
+        let all = leaves(7);
+        let old_checkpoint = checkpoint(&log_key, 4, mth(&all[..4]));
+        let new_checkpoint = checkpoint(&log_key, 7, mth(&all));
+
+        let mut nodes = consistency_proof_nodes(4, &all);
+        nodes[0] = [0xAAu8; 32];
+        let proof = ConsistencyProof { nodes };
+
+        assert_eq!(
+            verify_consistency(&old_checkpoint, &new_checkpoint, &proof),
+            Err(LogicError::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn consistency_of_identical_checkpoints_requires_empty_proof() {
+        let log_key = TestSigner::seeded(4);
+        let all = leaves(3);
+        let root = mth(&all);
+        let checkpoint = checkpoint(&log_key, 3, root);
+
+        let proof = ConsistencyProof { nodes: Vec::new() };
+        assert!(verify_consistency(&checkpoint, &checkpoint, &proof).is_ok());
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Error types shared across the crate.
+//!
+//! Every fallible operation in `saorsa-logic` returns [`LogicResult`] so that
+//! zkVM guest programs and native callers share one error surface.
+
+use core::fmt;
+
+/// Errors produced by the verification logic in this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicError {
+    /// A cryptographic signature failed to verify.
+    InvalidSignature,
+    /// A Merkle inclusion or consistency proof did not match the expected root.
+    InvalidProof,
+    /// A supplied hash did not match the expected content hash.
+    HashMismatch,
+    /// An input was malformed or out of range for the operation requested.
+    InvalidInput,
+    /// A time- or epoch-bounded credential was presented after it expired.
+    Expired,
+}
+
+impl fmt::Display for LogicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogicError::InvalidSignature => write!(f, "invalid signature"),
+            LogicError::InvalidProof => write!(f, "invalid proof"),
+            LogicError::HashMismatch => write!(f, "hash mismatch"),
+            LogicError::InvalidInput => write!(f, "invalid input"),
+            LogicError::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LogicError {}
+
+/// Convenience alias for results produced by this crate.
+pub type LogicResult<T> = Result<T, LogicError>;
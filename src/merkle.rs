@@ -0,0 +1,328 @@
+// Copyright 2024 Saorsa Labs Limited
+//
+// Licensed under the Apache License, Version 2.0 or MIT license, at your option.
+// This file may not be copied, modified, or distributed except according to those terms.
+
+//! Merkle tree construction and proof verification.
+//!
+//! This module provides two complementary ways to work with a Merkle tree:
+//!
+//! - **Static proofs** ([`verify_proof`]): given a full authentication path, confirm
+//!   that a leaf is included under a known root. This is what storage nodes ship
+//!   alongside a chunk so a verifier can check inclusion without the whole tree.
+//! - **Incremental accumulation** ([`Frontier`]): append leaves one at a time while
+//!   keeping only `O(log n)` state, so a node can fold in storage chunks as they
+//!   arrive instead of holding the entire tree in memory. This mirrors the
+//!   frontier/bridge approach used by the `incrementalmerkletree`/`bridgetree`
+//!   crates, and the root it produces after `N` appends is identical to the root
+//!   a full static build over the same `N` leaves would produce.
+//!
+//! Tree depth is bounded by [`MAX_DEPTH`], which is generous enough for any
+//! realistic chunk count while keeping all state stack-allocated for `no_std`.
+
+use crate::error::{LogicError, LogicResult};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A 32-byte hash as produced by the crate's domain-separated hash functions.
+pub type Hash = [u8; 32];
+
+/// Maximum supported tree depth (2^32 leaves), bounding all fixed-size state.
+pub const MAX_DEPTH: usize = 32;
+
+const LEAF_DOMAIN: u8 = 0x00;
+const NODE_DOMAIN: u8 = 0x01;
+
+/// Returns the deterministic padding hash for an empty subtree of height
+/// `level` (`empty_hash(0)` is the hash of the empty leaf).
+///
+/// These are fixed constants committed to by the crate, derived from the same
+/// domain-separated hash functions as real nodes, so every verifier pads
+/// missing siblings identically.
+#[must_use]
+pub fn empty_hash(level: usize) -> Hash {
+    let mut hash = leaf_hash(&[]);
+    for _ in 0..level {
+        hash = combine(&hash, &hash);
+    }
+    hash
+}
+
+/// Hashes a leaf's content with the leaf domain tag.
+#[must_use]
+pub fn leaf_hash(data: &[u8]) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_DOMAIN]);
+    hasher.update(data);
+    *hasher.finalize().as_bytes()
+}
+
+/// Combines two child hashes into their parent, with the node domain tag.
+#[must_use]
+pub fn combine(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_DOMAIN]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// A static inclusion proof: the sibling hash at each level from leaf to root.
+///
+/// `siblings[i]` is the sibling at level `i`; whether it is the left or right
+/// child at that level is determined by bit `i` of `leaf_index` (`0` = leaf is
+/// left child, sibling is on the right; `1` = leaf is right child).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Index of the leaf within the tree, counting from zero.
+    pub leaf_index: u64,
+    /// Sibling hashes from the leaf's level up to (but not including) the root.
+    pub siblings: Vec<Hash>,
+}
+
+/// Verifies that `leaf` is included under `root` according to `proof`.
+#[cfg(feature = "alloc")]
+pub fn verify_proof(leaf: &Hash, proof: &MerkleProof, root: &Hash) -> LogicResult<()> {
+    let mut current = *leaf;
+    let mut index = proof.leaf_index;
+    for sibling in &proof.siblings {
+        current = if index & 1 == 0 {
+            combine(&current, sibling)
+        } else {
+            combine(sibling, &current)
+        };
+        index >>= 1;
+    }
+    if &current == root {
+        Ok(())
+    } else {
+        Err(LogicError::InvalidProof)
+    }
+}
+
+/// An append-only incremental Merkle accumulator, tracked by its *frontier*.
+///
+/// The tree is modeled as a complete binary tree of fixed depth [`MAX_DEPTH`]:
+/// leaf slots beyond `leaf_count` are implicitly padded with [`empty_hash`],
+/// so [`Frontier::root`] always matches a full static build over the same
+/// leaves. The frontier stores, for each level, the rightmost completed
+/// subtree root at that level (or `None` if no subtree has been completed
+/// there yet), which is `O(log n)` state regardless of how many leaves have
+/// been appended.
+///
+/// [`Frontier::witness`] only proves the most recently appended leaf — the
+/// siblings its own append ripple-carried through are retained for exactly
+/// that purpose. Proving an arbitrary historical position after further
+/// appends needs a full authentication-path ("bridge") tracking structure,
+/// which this accumulator does not keep.
+#[derive(Debug, Clone)]
+pub struct Frontier {
+    /// Number of leaves appended so far.
+    leaf_count: u64,
+    /// `nodes[level]` is the rightmost completed subtree root at that level.
+    nodes: [Option<Hash>; MAX_DEPTH],
+    /// Siblings encountered while ripple-carrying the most recent append,
+    /// one per level climbed, lowest level first.
+    #[cfg(feature = "alloc")]
+    last_ripple: Vec<Hash>,
+}
+
+impl Default for Frontier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Frontier {
+    /// Creates an empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            leaf_count: 0,
+            nodes: [None; MAX_DEPTH],
+            #[cfg(feature = "alloc")]
+            last_ripple: Vec::new(),
+        }
+    }
+
+    /// Number of leaves appended so far.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Returns `true` if no leaves have been appended.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count == 0
+    }
+
+    /// Appends a leaf hash, updating the frontier in `O(log n)`.
+    ///
+    /// While a sibling already exists at the current level, the carried node
+    /// is combined with it (`hash(left ‖ right)`) and promoted one level up;
+    /// otherwise the carry is stored as the new rightmost subtree at that
+    /// level and the append completes.
+    pub fn append(&mut self, leaf: Hash) {
+        #[cfg(feature = "alloc")]
+        let mut ripple = Vec::new();
+
+        let mut carry = leaf;
+        let mut level = 0;
+        while let Some(existing) = self.nodes[level] {
+            #[cfg(feature = "alloc")]
+            ripple.push(existing);
+            carry = combine(&existing, &carry);
+            self.nodes[level] = None;
+            level += 1;
+        }
+
+        self.nodes[level] = Some(carry);
+        self.leaf_count += 1;
+        #[cfg(feature = "alloc")]
+        {
+            self.last_ripple = ripple;
+        }
+    }
+
+    /// Folds the frontier into the root of the tree built so far.
+    ///
+    /// Climbs the fixed-depth tree from level `0` to [`MAX_DEPTH`], combining
+    /// in the rightmost completed subtree at each level whose bit is set in
+    /// `leaf_count`, or [`empty_hash`] when it is not. This is the same
+    /// padded-complete-tree model a full static build over `leaf_count`
+    /// leaves (real leaves followed by empty padding) would fold down to.
+    #[must_use]
+    pub fn root(&self) -> Hash {
+        let mut acc = empty_hash(0);
+        for level in 0..MAX_DEPTH {
+            acc = if (self.leaf_count >> level) & 1 == 1 {
+                let sibling = self.nodes[level].expect("bit set in leaf_count implies a stored node");
+                combine(&sibling, &acc)
+            } else {
+                combine(&acc, &empty_hash(level))
+            };
+        }
+        acc
+    }
+
+    /// Emits an inclusion proof for the most recently appended leaf,
+    /// consumable by [`verify_proof`] against [`Frontier::root`].
+    ///
+    /// Only the tip of the frontier can be proven this way: appending another
+    /// leaf discards the ripple this relies on.
+    #[cfg(feature = "alloc")]
+    pub fn witness(&self) -> LogicResult<MerkleProof> {
+        if self.leaf_count == 0 {
+            return Err(LogicError::InvalidInput);
+        }
+        let position = self.leaf_count - 1;
+        let resting_level = self.last_ripple.len();
+
+        let mut siblings = Vec::with_capacity(MAX_DEPTH);
+        siblings.extend(self.last_ripple.iter().copied());
+        siblings.push(empty_hash(resting_level));
+        for level in (resting_level + 1)..MAX_DEPTH {
+            let sibling = if (self.leaf_count >> level) & 1 == 1 {
+                self.nodes[level].expect("bit set in leaf_count implies a stored node")
+            } else {
+                empty_hash(level)
+            };
+            siblings.push(sibling);
+        }
+
+        Ok(MerkleProof {
+            leaf_index: position,
+            siblings,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    /// Reference root of a fixed-depth complete tree over `leaves`, computed
+    /// independently of [`Frontier`], padding missing leaves with
+    /// [`empty_hash`]. Prunes subtrees that contain no real leaves, so it
+    /// stays cheap even at `depth == MAX_DEPTH`.
+    fn static_root(leaves: &[Hash], depth: usize, start: usize) -> Hash {
+        if start >= leaves.len() {
+            return empty_hash(depth);
+        }
+        if depth == 0 {
+            return leaves[start];
+        }
+        let half = 1usize << (depth - 1);
+        let left = static_root(leaves, depth - 1, start);
+        let right = static_root(leaves, depth - 1, start + half);
+        combine(&left, &right)
+    }
+
+    fn leaves(n: u64) -> Vec<Hash> {
+        (0..n).map(|i| leaf_hash(&i.to_le_bytes())).collect()
+    }
+
+    #[test]
+    fn empty_frontier_root_is_fully_padded_empty_hash() {
+        let frontier = Frontier::new();
+        assert_eq!(frontier.root(), empty_hash(MAX_DEPTH));
+        assert!(frontier.witness().is_err());
+    }
+
+    #[test]
+    fn root_matches_full_static_build() {
+        for n in 0..48u64 {
+            let mut frontier = Frontier::new();
+            let built = leaves(n);
+            for leaf in &built {
+                frontier.append(*leaf);
+            }
+            assert_eq!(
+                frontier.root(),
+                static_root(&built, MAX_DEPTH, 0),
+                "root mismatch after {n} appends"
+            );
+        }
+    }
+
+    #[test]
+    fn witness_verifies_against_root_for_tip_leaf() {
+        for n in 1..48u64 {
+            let mut frontier = Frontier::new();
+            let built = leaves(n);
+            for leaf in &built {
+                frontier.append(*leaf);
+            }
+            let tip = *built.last().unwrap();
+            let proof = frontier.witness().expect("non-empty frontier has a tip to witness");
+            assert_eq!(proof.leaf_index, n - 1);
+            verify_proof(&tip, &proof, &frontier.root())
+                .unwrap_or_else(|e| panic!("witness failed to verify after {n} appends: {e:?}"));
+        }
+    }
+
+    #[test]
+    fn witness_rejects_wrong_leaf() {
+        let mut frontier = Frontier::new();
+        frontier.append(leaf_hash(b"a"));
+        frontier.append(leaf_hash(b"b"));
+        let proof = frontier.witness().unwrap();
+        let wrong = leaf_hash(b"not-b");
+        assert_eq!(verify_proof(&wrong, &proof, &frontier.root()), Err(LogicError::InvalidProof));
+    }
+
+    #[test]
+    fn static_proof_round_trip() {
+        let built = leaves(5);
+        let mut frontier = Frontier::new();
+        for leaf in &built {
+            frontier.append(*leaf);
+        }
+        let root = frontier.root();
+        let proof = frontier.witness().unwrap();
+        assert!(verify_proof(&built[4], &proof, &root).is_ok());
+    }
+}